@@ -17,7 +17,7 @@
 //! A non-std set of HTTP types.
 
 use crate::Vec;
-use primitives::offchain::{Timestamp, HttpRequestId as RequestId, HttpRequestStatus as RequestStatus};
+use primitives::offchain::{Timestamp, Duration, HttpRequestId as RequestId, HttpRequestStatus as RequestStatus};
 
 /// Request method (HTTP verb)
 #[derive(Clone, PartialEq, Eq)]
@@ -54,6 +54,109 @@ fn from_utf8(chunk: &[u8]) -> Option<&str> {
 	std::str::from_utf8(chunk).ok()
 }
 
+/// Base64-encode `bytes` using the standard alphabet (RFC 4648), with padding.
+///
+/// Hand-rolled rather than pulled in from a crate so that [`Request::basic_auth`]
+/// stays usable from a `no_std` worker, the same way the rest of this module
+/// only reaches for external crates behind a cargo feature (`std`, `serde`).
+fn base64_encode(bytes: &[u8]) -> std::string::String {
+	const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+	let mut out = Vec::with_capacity((bytes.len() + 2) / 3 * 4);
+	for chunk in bytes.chunks(3) {
+		let b0 = chunk[0];
+		let b1 = chunk.get(1).copied().unwrap_or(0);
+		let b2 = chunk.get(2).copied().unwrap_or(0);
+
+		out.push(ALPHABET[(b0 >> 2) as usize]);
+		out.push(ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize]);
+		out.push(if chunk.len() > 1 { ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] } else { b'=' });
+		out.push(if chunk.len() > 2 { ALPHABET[(b2 & 0x3f) as usize] } else { b'=' });
+	}
+	std::string::String::from_utf8(out).expect("base64 alphabet is ASCII; qed")
+}
+
+/// Returns `true` if `b` is a valid RFC 7230 `tchar`.
+///
+/// `token = 1*tchar` and a header name must be a single `token`, i.e. no
+/// control characters, no separators (`:`, whitespace, `()<>@,;\"/[]?={}`, ...)
+/// and ASCII only.
+fn is_tchar(b: u8) -> bool {
+	match b {
+		b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.'
+		| b'^' | b'_' | b'`' | b'|' | b'~' => true,
+		b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' => true,
+		_ => false,
+	}
+}
+
+/// Error returned when a header name does not follow the RFC 7230 token grammar.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct InvalidHeaderName;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+enum HeaderNameRepr {
+	/// One of the well-known header names, kept as a `'static` string so that
+	/// constants like `HeaderName::CONTENT_TYPE` don't need to allocate.
+	Static(&'static str),
+	/// A name validated and copied from user-provided input.
+	Owned(Vec<u8>),
+}
+
+/// A validated HTTP header name.
+///
+/// Unlike a plain `&str`, constructing a `HeaderName` guarantees the value is
+/// a valid RFC 7230 token, so it can be sent as-is without risking a malformed
+/// request. Header names are case-insensitive, which is reflected in this
+/// type's `PartialEq`/`Eq` implementations.
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct HeaderName(HeaderNameRepr);
+
+impl HeaderName {
+	/// `Content-Type` header.
+	pub const CONTENT_TYPE: HeaderName = HeaderName(HeaderNameRepr::Static("Content-Type"));
+	/// `Authorization` header.
+	pub const AUTHORIZATION: HeaderName = HeaderName(HeaderNameRepr::Static("Authorization"));
+	/// `Accept` header.
+	pub const ACCEPT: HeaderName = HeaderName(HeaderNameRepr::Static("Accept"));
+	/// `User-Agent` header.
+	pub const USER_AGENT: HeaderName = HeaderName(HeaderNameRepr::Static("User-Agent"));
+	/// `Content-Encoding` header.
+	pub const CONTENT_ENCODING: HeaderName = HeaderName(HeaderNameRepr::Static("Content-Encoding"));
+	/// `Retry-After` header.
+	pub const RETRY_AFTER: HeaderName = HeaderName(HeaderNameRepr::Static("Retry-After"));
+	/// `Location` header.
+	pub const LOCATION: HeaderName = HeaderName(HeaderNameRepr::Static("Location"));
+
+	/// Create a new `HeaderName`, validating `name` against the RFC 7230 token grammar.
+	pub fn new(name: &str) -> Result<Self, InvalidHeaderName> {
+		if name.is_empty() || !name.bytes().all(is_tchar) {
+			return Err(InvalidHeaderName);
+		}
+		Ok(HeaderName(HeaderNameRepr::Owned(name.as_bytes().to_vec())))
+	}
+
+	/// Return the header name as a `&str`.
+	pub fn as_str(&self) -> &str {
+		match &self.0 {
+			HeaderNameRepr::Static(name) => name,
+			HeaderNameRepr::Owned(bytes) => from_utf8(bytes)
+				.expect("HeaderName is only constructed from a validated ASCII token; qed"),
+		}
+	}
+}
+
+impl PartialEq for HeaderName {
+	fn eq(&self, other: &Self) -> bool {
+		self.as_str().eq_ignore_ascii_case(other.as_str())
+	}
+}
+
+impl Eq for HeaderName {}
+
 /// An HTTP request builder.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -108,11 +211,75 @@ impl<'a, T: Default> Request<'a, T> {
 		self
 	}
 
+	/// Add a header using a validated [`HeaderName`].
+	///
+	/// Prefer this over [`add_header`](Self::add_header) when the header name is
+	/// known to be one of [`HeaderName`]'s constants or otherwise worth validating
+	/// against the RFC 7230 token grammar ahead of time.
+	pub fn header(&mut self, name: HeaderName, value: &str) -> &mut Self {
+		self.headers.push((name.as_str().as_bytes().to_vec(), value.as_bytes().to_vec()));
+		self
+	}
+
 	/// Set the deadline of the request.
 	pub fn deadline(&mut self, deadline: Timestamp) -> &mut Self {
 		self.deadline = Some(deadline);
 		self
 	}
+
+	/// Add an `Authorization: Bearer <token>` header.
+	pub fn bearer_auth(&mut self, token: &str) -> &mut Self {
+		let mut value = std::string::String::from("Bearer ");
+		value.push_str(token);
+		self.header(HeaderName::AUTHORIZATION, &value)
+	}
+
+	/// Add an `Authorization: Basic <base64(username:password)>` header.
+	///
+	/// `password` is optional, matching RFC 7617's `user-pass = userid ":" *TEXT`.
+	pub fn basic_auth(&mut self, username: &str, password: Option<&str>) -> &mut Self {
+		let mut credentials = std::string::String::from(username);
+		credentials.push(':');
+		if let Some(password) = password {
+			credentials.push_str(password);
+		}
+
+		let mut value = std::string::String::from("Basic ");
+		value.push_str(&base64_encode(credentials.as_bytes()));
+		self.header(HeaderName::AUTHORIZATION, &value)
+	}
+}
+
+impl<'a, T> Request<'a, T> {
+	/// Set this request's body to a `multipart/form-data` encoding of `form`,
+	/// setting the `Content-Type` header (with the generated boundary) automatically.
+	///
+	/// Replaces any `Content-Type` header already set on this request.
+	///
+	/// `form` must be kept alive for as long as the returned request, the same
+	/// way `url` and raw `body` chunks already borrow from the caller's scope.
+	pub fn form(self, form: &'a Form) -> Request<'a, [&'a [u8]; 1]> {
+		let Request { method, url, deadline, mut headers, .. } = self;
+		set_content_type(&mut headers, form.content_type().into_bytes());
+		Request { method, url, body: [form.payload.as_slice()], deadline, headers }
+	}
+
+	/// Set this request's body to an `application/x-www-form-urlencoded`
+	/// encoding of `form`, setting the `Content-Type` header automatically.
+	///
+	/// Replaces any `Content-Type` header already set on this request.
+	pub fn urlencoded(self, form: &'a UrlEncodedForm) -> Request<'a, [&'a [u8]; 1]> {
+		let Request { method, url, deadline, mut headers, .. } = self;
+		set_content_type(&mut headers, b"application/x-www-form-urlencoded".to_vec());
+		Request { method, url, body: [form.payload.as_slice()], deadline, headers }
+	}
+}
+
+/// Remove any existing `Content-Type` entry from `headers`, then push `value`,
+/// so a request never ends up carrying two `Content-Type` headers.
+fn set_content_type(headers: &mut Vec<(Vec<u8>, Vec<u8>)>, value: Vec<u8>) {
+	headers.retain(|(name, _)| !from_utf8(name).map_or(false, |name| name.eq_ignore_ascii_case(HeaderName::CONTENT_TYPE.as_str())));
+	headers.push((HeaderName::CONTENT_TYPE.as_str().as_bytes().to_vec(), value));
 }
 
 impl<'a, 'b, T: IntoIterator<Item=&'b [u8]>> Request<'a, T> {
@@ -149,6 +316,208 @@ impl<'a, 'b, T: IntoIterator<Item=&'b [u8]>> Request<'a, T> {
 	}
 }
 
+impl<'a, 'b, T: Clone + IntoIterator<Item=&'b [u8]>> Request<'a, T> {
+	/// Send the request, retrying according to `policy` on transport errors or
+	/// response status codes configured in [`RetryPolicy::retry_statuses`].
+	///
+	/// Uses truncated exponential backoff with full jitter between attempts
+	/// (delay for attempt `n` is a uniformly random value in `[0, min(base * 2^n, cap)]`),
+	/// honouring a `Retry-After` response header (in seconds) as a floor for the delay.
+	/// Aborts with the last error once `deadline` passes or `policy.max_attempts`
+	/// is exhausted.
+	pub fn send_with_retry(self, policy: &RetryPolicy) -> Result<Response, Error> {
+		let deadline = self.deadline;
+		let mut attempt = 0;
+
+		loop {
+			let outcome = self.clone().send()
+				.map_err(|()| Error::DeadlineReached)
+				.and_then(|pending| pending.try_wait(deadline).map_err(|_| Error::DeadlineReached)?);
+
+			let (response_or_err, retry_after) = match outcome {
+				Ok(mut response) => {
+					let retry_after = response.headers().get(&HeaderName::RETRY_AFTER).and_then(|v| v.parse().ok());
+					(Ok(response), retry_after)
+				},
+				Err(err) => (Err(err), None),
+			};
+
+			let is_retryable = match &response_or_err {
+				Ok(response) => policy.should_retry_status(response.code),
+				Err(Error::DeadlineReached) => false,
+				Err(Error::Timeout) | Err(Error::Unknown) => true,
+				Err(Error::Redirect) | Err(Error::Decode) => false,
+			};
+
+			attempt += 1;
+			if !is_retryable || attempt >= policy.max_attempts {
+				return response_or_err;
+			}
+
+			if let Some(deadline) = deadline {
+				if crate::timestamp() >= deadline {
+					return response_or_err;
+				}
+			}
+
+			let delay_ms = policy.delay_for(attempt - 1, retry_after, &crate::random_seed());
+			if delay_ms > 0 {
+				crate::sleep_until(crate::timestamp().add(Duration::from_millis(delay_ms)));
+			}
+		}
+	}
+}
+
+impl<'a, 'b, T: Default + Clone + IntoIterator<Item=&'b [u8]>> Request<'a, T> {
+	/// Send the request, automatically following redirects according to `policy`.
+	///
+	/// Implements the standard method-rewrite rules: a `303` (and, for
+	/// compatibility, a `301`/`302` on a non-GET/HEAD request) downgrades the
+	/// method to `GET` and drops the body, while `307`/`308` preserve both the
+	/// method and the body. `Authorization`/`Cookie` headers are stripped on
+	/// cross-origin hops. Returns `Error::Redirect` on a redirect loop or once
+	/// the policy's hop limit is exceeded.
+	pub fn send_following_redirects(self, policy: RedirectPolicy) -> Result<Response, Error> {
+		let Request { mut method, url, body, deadline, mut headers } = self;
+		let mut url = url.as_bytes().to_vec();
+		let mut body = body;
+		let mut visited: Vec<Vec<u8>> = Vec::new();
+		let mut hop = 0;
+
+		loop {
+			let id = crate::http_request_start(
+				method.as_ref(),
+				from_utf8(&url).ok_or(Error::Redirect)?,
+				&[],
+			);
+			for (name, value) in &headers {
+				crate::http_request_add_header(
+					id,
+					from_utf8(name).expect("Header names are always Vecs created from valid str; qed"),
+					from_utf8(value).expect("Header values are always Vecs created from valid str; qed"),
+				)
+			}
+			for chunk in body.clone() {
+				crate::http_request_write_body(id, chunk, deadline).map_err(|()| Error::DeadlineReached)?;
+			}
+			crate::http_request_write_body(id, &[], deadline).map_err(|()| Error::DeadlineReached)?;
+
+			let pending = PendingRequest { id };
+			let mut response = match pending.try_wait(deadline) {
+				Ok(Ok(response)) => response,
+				Ok(Err(err)) => return Err(err),
+				Err(_pending) => return Err(Error::DeadlineReached),
+			};
+
+			if !is_redirect_status(response.code) {
+				response.effective_url = Some(url);
+				return Ok(response);
+			}
+
+			if let RedirectPolicy::None = policy {
+				response.effective_url = Some(url);
+				return Ok(response);
+			}
+
+			let location = match response.headers().get(&HeaderName::LOCATION) {
+				Some(location) => location.to_owned(),
+				None => { response.effective_url = Some(url); return Ok(response); },
+			};
+			let next_url = resolve_url(
+				from_utf8(&url).ok_or(Error::Redirect)?,
+				&location,
+			).ok_or(Error::Redirect)?;
+
+			match policy {
+				RedirectPolicy::Limited(max) if hop >= max => return Err(Error::Redirect),
+				RedirectPolicy::Custom(should_follow) => {
+					let next_url_str = from_utf8(&next_url).ok_or(Error::Redirect)?;
+					if !should_follow(next_url_str, hop) {
+						response.effective_url = Some(url);
+						return Ok(response);
+					}
+				},
+				RedirectPolicy::None | RedirectPolicy::Limited(_) => {},
+			}
+
+			if visited.contains(&next_url) {
+				return Err(Error::Redirect);
+			}
+			visited.push(core::mem::replace(&mut url, next_url));
+			hop += 1;
+
+			if !same_origin(visited.last().expect("just pushed; qed"), &url) {
+				headers.retain(|(name, _)| {
+					let name = from_utf8(name).unwrap_or("");
+					!name.eq_ignore_ascii_case("Authorization") && !name.eq_ignore_ascii_case("Cookie")
+				});
+			}
+
+			// 307/308 (and a GET/HEAD 301/302) preserve method and body as-is.
+			if response.code == 303 || (matches!(response.code, 301 | 302) && !method_is_get_or_head(&method)) {
+				method = Method::Get;
+				body = T::default();
+			}
+		}
+	}
+}
+
+/// A policy describing how [`Request::send_with_retry`] should retry failed requests.
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RetryPolicy {
+	/// Maximum number of attempts (including the first one) before giving up.
+	pub max_attempts: u32,
+	/// Response status codes that should trigger a retry, in addition to
+	/// transport-level `Error::Timeout`/`Error::Unknown`.
+	pub retry_statuses: Vec<u16>,
+	/// Base delay, in milliseconds, for the exponential backoff schedule.
+	pub base_delay_ms: u64,
+	/// Upper bound, in milliseconds, for the backoff delay (before jitter).
+	/// A larger `Retry-After` floor still takes precedence.
+	pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		RetryPolicy {
+			max_attempts: 3,
+			retry_statuses: vec![429, 502, 503, 504],
+			base_delay_ms: 250,
+			max_delay_ms: 10_000,
+		}
+	}
+}
+
+impl RetryPolicy {
+	/// A policy that never retries; `send_with_retry` behaves like a single `send`.
+	pub fn none() -> Self {
+		RetryPolicy { max_attempts: 1, ..Default::default() }
+	}
+
+	fn should_retry_status(&self, code: u16) -> bool {
+		self.retry_statuses.contains(&code)
+	}
+
+	/// Compute the jittered delay (in milliseconds) before the attempt following
+	/// attempt `attempt` (0-based), honouring an optional `Retry-After` floor.
+	fn delay_for(&self, attempt: u32, retry_after_secs: Option<u64>, entropy: &[u8]) -> u64 {
+		let factor = 1u64.checked_shl(attempt).unwrap_or(u64::max_value());
+		let delay = self.base_delay_ms.saturating_mul(factor).min(self.max_delay_ms);
+		let floor = retry_after_secs.map(|secs| secs.saturating_mul(1000)).unwrap_or(0);
+
+		if delay == 0 {
+			return floor;
+		}
+
+		// Full jitter: pick a uniformly random value in `[0, delay]` from the
+		// host-provided entropy, so retries fan out across clients without
+		// needing a randomness source the caller's worker may not have.
+		let seed = entropy.iter().fold(0u64, |acc, &byte| acc.wrapping_mul(31).wrapping_add(byte as u64));
+		(seed % (delay + 1)).max(floor)
+	}
+}
+
 /// A request error
 pub enum Error {
 	/// Deadline has been reached.
@@ -157,6 +526,300 @@ pub enum Error {
 	Timeout,
 	/// Unknown error has been ecountered.
 	Unknown,
+	/// Redirect handling failed: the policy's hop limit was exceeded, a
+	/// redirect loop was detected, or a `Location` header could not be
+	/// resolved to a URL.
+	Redirect,
+	/// Decoding the response body failed: a compressed stream was truncated
+	/// or otherwise not valid for its `Content-Encoding`, or the body was not
+	/// valid UTF-8 (`text`) or JSON (`json`).
+	Decode,
+}
+
+/// Controls whether and how `Request::send_following_redirects` follows
+/// `3xx` responses, modeled on the `Policy` types used by `reqwest`/`deno`.
+#[derive(Clone, Copy)]
+pub enum RedirectPolicy {
+	/// Never follow redirects; behaves like `send()`.
+	None,
+	/// Follow up to the given number of redirects before giving up with `Error::Redirect`.
+	Limited(usize),
+	/// Decide per-hop whether to follow the redirect to `url`, given the number
+	/// of hops already followed.
+	Custom(fn(url: &str, hop: usize) -> bool),
+}
+
+impl Default for RedirectPolicy {
+	fn default() -> Self {
+		RedirectPolicy::Limited(10)
+	}
+}
+
+fn is_redirect_status(code: u16) -> bool {
+	matches!(code, 301 | 302 | 303 | 307 | 308)
+}
+
+fn method_is_get_or_head(method: &Method) -> bool {
+	match method {
+		Method::Get => true,
+		Method::Other(m) => m.eq_ignore_ascii_case("HEAD"),
+		_ => false,
+	}
+}
+
+/// The `scheme`/`authority`/`path` components of a URL, used to resolve
+/// redirect targets and detect cross-origin hops.
+struct UrlParts<'a> {
+	scheme: &'a str,
+	authority: &'a str,
+	path: &'a str,
+}
+
+fn parse_url(url: &str) -> Option<UrlParts> {
+	let scheme_end = url.find("://")?;
+	let rest = &url[scheme_end + 3..];
+	let path_start = rest.find(|c| c == '/' || c == '?' || c == '#').unwrap_or_else(|| rest.len());
+	let path = &rest[path_start..];
+	Some(UrlParts {
+		scheme: &url[..scheme_end],
+		authority: &rest[..path_start],
+		path: if path.is_empty() { "/" } else { path },
+	})
+}
+
+fn same_origin(a: &[u8], b: &[u8]) -> bool {
+	match (from_utf8(a).and_then(parse_url), from_utf8(b).and_then(parse_url)) {
+		(Some(a), Some(b)) => a.scheme.eq_ignore_ascii_case(b.scheme)
+			&& a.authority.eq_ignore_ascii_case(b.authority),
+		_ => false,
+	}
+}
+
+/// Resolve a `Location` header value against the URL it was received from.
+///
+/// Supports absolute URLs, protocol-relative (`//host/path`), absolute-path
+/// (`/path`) and relative (`path`) references.
+fn resolve_url(base: &str, location: &str) -> Option<Vec<u8>> {
+	if is_absolute_url(location) {
+		return Some(location.as_bytes().to_vec());
+	}
+
+	let base = parse_url(base)?;
+	let mut out = Vec::new();
+	out.extend_from_slice(base.scheme.as_bytes());
+	out.extend_from_slice(b"://");
+
+	if let Some(rest) = location.strip_prefix("//") {
+		out.extend_from_slice(rest.as_bytes());
+		return Some(out);
+	}
+
+	out.extend_from_slice(base.authority.as_bytes());
+	if location.starts_with('/') {
+		out.extend_from_slice(location.as_bytes());
+	} else {
+		let dir = match base.path.rfind('/') {
+			Some(idx) => &base.path[..=idx],
+			None => "/",
+		};
+		out.extend_from_slice(dir.as_bytes());
+		out.extend_from_slice(location.as_bytes());
+	}
+	Some(out)
+}
+
+/// Whether `location` is an absolute URL, i.e. begins with an RFC 3986
+/// `scheme:` followed by `//`, rather than merely containing `://` somewhere
+/// inside it (as a same-origin redirect's query string might, e.g.
+/// `/cb?next=http://evil.example`).
+fn is_absolute_url(location: &str) -> bool {
+	let colon = match location.find(':') {
+		Some(colon) => colon,
+		None => return false,
+	};
+	let scheme = &location[..colon];
+	let scheme_is_valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+		&& scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.');
+	scheme_is_valid && location[colon + 1..].starts_with("//")
+}
+
+/// A builder for a `multipart/form-data` request body, for use with [`Request::form`].
+///
+/// Accumulates text fields and file parts under a freshly generated boundary,
+/// serializing each one as it is added so the form can be attached to a
+/// request without a separate "finish" step.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Form {
+	boundary: Vec<u8>,
+	payload: Vec<u8>,
+}
+
+impl Default for Form {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Form {
+	/// Create an empty form with a freshly generated boundary.
+	pub fn new() -> Self {
+		let mut form = Form { boundary: generate_boundary(), payload: Vec::new() };
+		form.write_terminator();
+		form
+	}
+
+	/// Add a plain text field.
+	pub fn text(&mut self, name: &str, value: &str) -> &mut Self {
+		self.truncate_terminator();
+		self.write_part_header(name, None, None);
+		self.payload.extend_from_slice(value.as_bytes());
+		self.payload.extend_from_slice(b"\r\n");
+		self.write_terminator();
+		self
+	}
+
+	/// Add a file part, with an optional filename and content type.
+	pub fn file(
+		&mut self,
+		name: &str,
+		filename: Option<&str>,
+		content_type: Option<&str>,
+		data: &[u8],
+	) -> &mut Self {
+		self.truncate_terminator();
+		self.write_part_header(name, filename, content_type);
+		self.payload.extend_from_slice(data);
+		self.payload.extend_from_slice(b"\r\n");
+		self.write_terminator();
+		self
+	}
+
+	/// The `Content-Type` header value for this form, including its boundary.
+	pub fn content_type(&self) -> std::string::String {
+		let mut content_type = std::string::String::from("multipart/form-data; boundary=");
+		content_type.push_str(from_utf8(&self.boundary).expect("boundary is ASCII; qed"));
+		content_type
+	}
+
+	fn write_part_header(&mut self, name: &str, filename: Option<&str>, content_type: Option<&str>) {
+		self.payload.extend_from_slice(b"--");
+		self.payload.extend_from_slice(&self.boundary);
+		self.payload.extend_from_slice(b"\r\n");
+		self.payload.extend_from_slice(b"Content-Disposition: form-data; name=\"");
+		write_quoted_escaped(&mut self.payload, name);
+		self.payload.extend_from_slice(b"\"");
+		if let Some(filename) = filename {
+			self.payload.extend_from_slice(b"; filename=\"");
+			write_quoted_escaped(&mut self.payload, filename);
+			self.payload.extend_from_slice(b"\"");
+		}
+		self.payload.extend_from_slice(b"\r\n");
+		if let Some(content_type) = content_type {
+			self.payload.extend_from_slice(b"Content-Type: ");
+			write_header_value_safe(&mut self.payload, content_type);
+			self.payload.extend_from_slice(b"\r\n");
+		}
+		self.payload.extend_from_slice(b"\r\n");
+	}
+
+	/// Drop the closing boundary so a new part can be appended in its place.
+	fn truncate_terminator(&mut self) {
+		let terminator_len = self.boundary.len() + 6; // "--" + boundary + "--" + "\r\n"
+		let new_len = self.payload.len() - terminator_len;
+		self.payload.truncate(new_len);
+	}
+
+	/// Append the closing boundary (`--boundary--\r\n`), marking the form as complete.
+	fn write_terminator(&mut self) {
+		self.payload.extend_from_slice(b"--");
+		self.payload.extend_from_slice(&self.boundary);
+		self.payload.extend_from_slice(b"--\r\n");
+	}
+}
+
+/// Write `value` into a `Content-Disposition` quoted-string parameter
+/// (`name="..."`/`filename="..."`), backslash-escaping `"` and `\` and
+/// dropping CR/LF so a crafted field name or filename can't break out of the
+/// quotes or inject extra header lines/parts into the multipart body.
+fn write_quoted_escaped(out: &mut Vec<u8>, value: &str) {
+	for &byte in value.as_bytes() {
+		match byte {
+			b'\r' | b'\n' => {},
+			b'"' | b'\\' => {
+				out.push(b'\\');
+				out.push(byte);
+			},
+			_ => out.push(byte),
+		}
+	}
+}
+
+/// Write `value` into an unquoted header line (`Content-Type: ...`), dropping
+/// CR/LF so it can't inject an extra header or part into the multipart body.
+fn write_header_value_safe(out: &mut Vec<u8>, value: &str) {
+	out.extend(value.bytes().filter(|&byte| byte != b'\r' && byte != b'\n'));
+}
+
+/// Generate a boundary string unlikely to collide with any part's content,
+/// using the host-provided entropy as a source of randomness for the hex suffix.
+fn generate_boundary() -> Vec<u8> {
+	const HEX: &[u8; 16] = b"0123456789abcdef";
+	let mut boundary = b"----SubstrateFormBoundary".to_vec();
+	for byte in crate::random_seed().iter().take(16) {
+		boundary.push(HEX[(byte >> 4) as usize]);
+		boundary.push(HEX[(byte & 0x0f) as usize]);
+	}
+	boundary
+}
+
+/// A builder for an `application/x-www-form-urlencoded` request body, for use
+/// with [`Request::urlencoded`].
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct UrlEncodedForm {
+	payload: Vec<u8>,
+}
+
+impl Default for UrlEncodedForm {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl UrlEncodedForm {
+	/// Create an empty urlencoded form.
+	pub fn new() -> Self {
+		UrlEncodedForm { payload: Vec::new() }
+	}
+
+	/// Append a key/value pair, percent-encoding both per the standard
+	/// `application/x-www-form-urlencoded` set.
+	pub fn append(&mut self, key: &str, value: &str) -> &mut Self {
+		if !self.payload.is_empty() {
+			self.payload.push(b'&');
+		}
+		percent_encode_form(&mut self.payload, key.as_bytes());
+		self.payload.push(b'=');
+		percent_encode_form(&mut self.payload, value.as_bytes());
+		self
+	}
+}
+
+/// Percent-encode `bytes` into `out` per the `application/x-www-form-urlencoded`
+/// set: ASCII alphanumerics and `*`, `-`, `.`, `_` pass through, space becomes
+/// `+`, everything else is escaped as `%XX`.
+fn percent_encode_form(out: &mut Vec<u8>, bytes: &[u8]) {
+	const HEX: &[u8; 16] = b"0123456789ABCDEF";
+	for &byte in bytes {
+		match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => out.push(byte),
+			b' ' => out.push(b'+'),
+			_ => {
+				out.push(b'%');
+				out.push(HEX[(byte >> 4) as usize]);
+				out.push(HEX[(byte & 0x0f) as usize]);
+			},
+		}
+	}
 }
 
 /// A struct representing an uncompleted http request.
@@ -218,6 +881,10 @@ pub struct Response {
 	pub code: u16,
 	/// A collection of headers.
 	headers: Option<Headers>,
+	/// The URL this response was actually served from, i.e. the last URL
+	/// visited when following redirects. `None` unless the request was sent
+	/// via `Request::send_following_redirects`.
+	effective_url: Option<Vec<u8>>,
 }
 
 impl Response {
@@ -226,6 +893,7 @@ impl Response {
 			id,
 			code,
 			headers: None,
+			effective_url: None,
 		}
 	}
 
@@ -237,10 +905,49 @@ impl Response {
 		self.headers.as_ref().expect("Headers were just set; qed")
 	}
 
+	/// The final URL this response was served from, after following any redirects.
+	///
+	/// `None` unless the request was sent via `Request::send_following_redirects`.
+	pub fn effective_url(&self) -> Option<&str> {
+		self.effective_url.as_ref().and_then(|url| from_utf8(url))
+	}
+
 	/// Retrieve the body of this response.
 	pub fn body(&self) -> ResponseBody {
 		ResponseBody::new(self.id)
 	}
+
+	/// Retrieve the body of this response, transparently decompressing it
+	/// according to its `Content-Encoding` header (`gzip`, `deflate`, `br`).
+	///
+	/// Decoding happens as the underlying 4096-byte chunks arrive, preserving
+	/// the deadline semantics of `body()`. If there's no `Content-Encoding`
+	/// header, or it isn't recognised, bytes are passed through unchanged.
+	#[cfg(feature = "std")]
+	pub fn decoded_body(&mut self) -> DecodedResponseBody {
+		let encoding = self.headers().get(&HeaderName::CONTENT_ENCODING).map(|encoding| encoding.to_owned());
+		DecodedResponseBody::new(self.body(), encoding)
+	}
+
+	/// Buffer the whole body and deserialize it as JSON.
+	///
+	/// Mirrors the typed-response-body helpers of higher-level HTTP clients,
+	/// turning the common oracle/price-feed pattern of "GET, parse JSON" into
+	/// a single call.
+	#[cfg(feature = "serde")]
+	pub fn json<D: serde::de::DeserializeOwned>(&mut self) -> Result<D, Error> {
+		let bytes = self.body().body_bytes()?;
+		decode_json(&bytes)
+	}
+}
+
+/// Deserialize `bytes` as JSON, mapping a parse failure to `Error::Decode`.
+///
+/// Split out from [`Response::json`] so the deserialization itself can be
+/// unit tested against an in-memory buffer, without a live response body.
+#[cfg(feature = "serde")]
+fn decode_json<D: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<D, Error> {
+	serde_json::from_slice(bytes).map_err(|_| Error::Decode)
 }
 
 /// A buffered byte iterator over response body.
@@ -275,6 +982,32 @@ impl ResponseBody {
 	pub fn is_deadline_reached(&self) -> bool {
 		self.is_deadline_reached
 	}
+
+	/// Drain the whole body into a `Vec<u8>`, respecting the deadline.
+	///
+	/// Returns `Err(Error::DeadlineReached)` if the deadline is hit before the
+	/// body has been fully read.
+	pub fn body_bytes(mut self) -> Result<Vec<u8>, Error> {
+		let bytes = self.by_ref().collect();
+		if self.is_deadline_reached() {
+			Err(Error::DeadlineReached)
+		} else {
+			Ok(bytes)
+		}
+	}
+
+	/// Drain the whole body and validate it as UTF-8 text.
+	pub fn text(self) -> Result<std::string::String, Error> {
+		decode_utf8(self.body_bytes()?)
+	}
+}
+
+/// Validate `bytes` as UTF-8, mapping a validation failure to `Error::Decode`.
+///
+/// Split out from [`ResponseBody::text`] so the validation itself can be unit
+/// tested directly against byte slices, without a live response body.
+fn decode_utf8(bytes: Vec<u8>) -> Result<std::string::String, Error> {
+	std::string::String::from_utf8(bytes).map_err(|_| Error::Decode)
 }
 
 impl Iterator for ResponseBody {
@@ -307,6 +1040,130 @@ impl Iterator for ResponseBody {
 	}
 }
 
+/// Adapts a [`ResponseBody`]'s `Iterator<Item=u8>` into `std::io::Read`, so it
+/// can feed a streaming decompressor without buffering the whole body.
+///
+/// Also tracks whether the underlying body stopped because the request's
+/// deadline was reached, so [`DecodedResponseBody::is_deadline_reached`] can
+/// tell that apart from the decoder hitting a genuinely truncated stream.
+///
+/// `pub` only so it can stand as [`DecodedResponseBody`]'s default type
+/// parameter; its fields are private and it has no public constructor.
+#[cfg(feature = "std")]
+pub struct ResponseBodyRead {
+	body: ResponseBody,
+	deadline_reached: std::rc::Rc<std::cell::Cell<bool>>,
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for ResponseBodyRead {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let mut written = 0;
+		while written < buf.len() {
+			match self.body.next() {
+				Some(byte) => {
+					buf[written] = byte;
+					written += 1;
+				},
+				None => break,
+			}
+		}
+		if self.body.is_deadline_reached() {
+			self.deadline_reached.set(true);
+		}
+		Ok(written)
+	}
+}
+
+#[cfg(feature = "std")]
+enum DecodedResponseBodyInner<R: std::io::Read> {
+	/// No recognised `Content-Encoding`; bytes are passed through unchanged.
+	Identity(R),
+	Gzip(flate2::read::GzDecoder<R>),
+	Deflate(flate2::read::DeflateDecoder<R>),
+	Brotli(brotli::Decompressor<R>),
+}
+
+/// A byte iterator over a response body, transparently decompressed
+/// according to the `Content-Encoding` it was constructed with.
+///
+/// Yields `Err(Error::Decode)` instead of panicking if the compressed stream
+/// turns out to be truncated or otherwise invalid.
+///
+/// Generic over the underlying reader so the decode/error-surfacing logic can
+/// be driven by an in-memory buffer in tests; [`Response::decoded_body`]
+/// always produces one wrapping a live [`ResponseBody`] (the `R` default).
+#[cfg(feature = "std")]
+pub struct DecodedResponseBody<R: std::io::Read = ResponseBodyRead> {
+	inner: DecodedResponseBodyInner<R>,
+	errored: bool,
+	deadline_reached: Option<std::rc::Rc<std::cell::Cell<bool>>>,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> DecodedResponseBody<R> {
+	fn from_reader(read: R, encoding: Option<&str>) -> Self {
+		let inner = match encoding {
+			Some("gzip") => DecodedResponseBodyInner::Gzip(flate2::read::GzDecoder::new(read)),
+			Some("deflate") => DecodedResponseBodyInner::Deflate(flate2::read::DeflateDecoder::new(read)),
+			Some("br") => DecodedResponseBodyInner::Brotli(brotli::Decompressor::new(read, 4096)),
+			_ => DecodedResponseBodyInner::Identity(read),
+		};
+		DecodedResponseBody { inner, errored: false, deadline_reached: None }
+	}
+}
+
+#[cfg(feature = "std")]
+impl DecodedResponseBody<ResponseBodyRead> {
+	fn new(body: ResponseBody, encoding: Option<std::string::String>) -> Self {
+		let deadline_reached = std::rc::Rc::new(std::cell::Cell::new(false));
+		let read = ResponseBodyRead { body, deadline_reached: deadline_reached.clone() };
+		let mut decoded = Self::from_reader(read, encoding.as_deref());
+		decoded.deadline_reached = Some(deadline_reached);
+		decoded
+	}
+
+	/// Whether iteration stopped because the request's deadline was reached,
+	/// rather than because the stream ended or was invalid.
+	///
+	/// A tripped deadline surfaces mid-stream as an ordinary-looking EOF (or,
+	/// for a compressed encoding, as a decode error), so callers need this to
+	/// tell a timeout apart from a genuinely truncated/corrupt stream.
+	pub fn is_deadline_reached(&self) -> bool {
+		self.deadline_reached.as_ref().map_or(false, |flag| flag.get())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Iterator for DecodedResponseBody<R> {
+	type Item = Result<u8, Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		use std::io::Read;
+
+		if self.errored {
+			return None;
+		}
+
+		let mut byte = [0_u8; 1];
+		let read = match &mut self.inner {
+			DecodedResponseBodyInner::Identity(read) => read.read(&mut byte),
+			DecodedResponseBodyInner::Gzip(decoder) => decoder.read(&mut byte),
+			DecodedResponseBodyInner::Deflate(decoder) => decoder.read(&mut byte),
+			DecodedResponseBodyInner::Brotli(decoder) => decoder.read(&mut byte),
+		};
+
+		match read {
+			Ok(0) => None,
+			Ok(_) => Some(Ok(byte[0])),
+			Err(_) => {
+				self.errored = true;
+				Some(Err(Error::Decode))
+			},
+		}
+	}
+}
+
 /// A collection of Headers in the response.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -330,6 +1187,21 @@ impl Headers {
 		None
 	}
 
+	/// Retrieve a single header from the list of headers, matching `name`
+	/// case-insensitively as required by RFC 7230.
+	///
+	/// Note this method is linearly looking from all the headers.
+	/// If you want to consume multiple headers it's better to iterate
+	/// and collect them on your own.
+	pub fn get(&self, name: &HeaderName) -> Option<&str> {
+		for &(ref key, ref val) in &self.raw {
+			if from_utf8(&key).map_or(false, |key| key.eq_ignore_ascii_case(name.as_str())) {
+				return from_utf8(&val)
+			}
+		}
+		None
+	}
+
 	/// Convert this headers into an iterator.
 	pub fn into_iter(&self) -> HeadersIterator {
 		HeadersIterator { collection: &self.raw, index: None }
@@ -365,8 +1237,343 @@ impl<'a> HeadersIterator<'a> {
 
 #[cfg(test)]
 mod tests {
+	use super::*;
+
+	#[test]
+	fn header_name_accepts_valid_tokens() {
+		assert!(HeaderName::new("X-Custom-Header").is_ok());
+		assert!(HeaderName::new("X_Custom.Header~1").is_ok());
+	}
+
+	#[test]
+	fn header_name_rejects_invalid_tokens() {
+		assert_eq!(HeaderName::new(""), Err(InvalidHeaderName));
+		assert_eq!(HeaderName::new("X-Header: Oops"), Err(InvalidHeaderName));
+		assert_eq!(HeaderName::new("X-Header\r\n"), Err(InvalidHeaderName));
+	}
+
+	#[test]
+	fn header_name_eq_is_case_insensitive() {
+		let lower = HeaderName::new("content-type").unwrap();
+		assert_eq!(lower, HeaderName::CONTENT_TYPE);
+	}
+
+	#[test]
+	fn delay_for_backs_off_exponentially_before_the_cap() {
+		let policy = RetryPolicy { base_delay_ms: 100, max_delay_ms: 10_000, ..Default::default() };
+		// With no entropy (`seed == 0`) the jitter always picks the bottom of the range, `0`.
+		assert_eq!(policy.delay_for(0, None, &[]), 0);
+		assert_eq!(policy.delay_for(1, None, &[]), 0);
+		assert_eq!(policy.delay_for(2, None, &[]), 0);
+		// This entropy folds to `seed == 8_140_700`, one less than a multiple of
+		// 101, 201 and 401, so the jitter picks the top of each range and exposes
+		// the doubling backoff delay itself.
+		let top = [8, 25, 8, 2, 7];
+		assert_eq!(policy.delay_for(0, None, &top), 100);
+		assert_eq!(policy.delay_for(1, None, &top), 200);
+		assert_eq!(policy.delay_for(2, None, &top), 400);
+	}
+
+	#[test]
+	fn delay_for_jitter_never_exceeds_the_backoff_delay() {
+		let policy = RetryPolicy { base_delay_ms: 100, max_delay_ms: 1_000, ..Default::default() };
+		for attempt in 0..8 {
+			let delay = policy.delay_for(attempt, None, &[1, 2, 3, 4, 5]);
+			let backoff = (policy.base_delay_ms << attempt).min(policy.max_delay_ms);
+			assert!(delay <= backoff);
+		}
+	}
+
+	#[test]
+	fn delay_for_honours_retry_after_as_a_floor() {
+		let policy = RetryPolicy { base_delay_ms: 100, max_delay_ms: 10_000, ..Default::default() };
+		// Attempt `0`'s backoff alone is only 100ms, so jitter could never exceed
+		// that (this entropy picks 46ms); the `Retry-After: 5` (seconds) floor
+		// lifts the delay to 5000ms.
+		let jittered = policy.delay_for(0, Some(5), &[200, 7]);
+		assert_eq!(jittered, 5_000);
+		assert!(jittered > policy.base_delay_ms);
+	}
+
+	#[test]
+	fn delay_for_caps_at_max_delay_ms() {
+		let policy = RetryPolicy { base_delay_ms: 100, max_delay_ms: 1_000, ..Default::default() };
+		assert_eq!(policy.delay_for(10, None, &[]), 0);
+		assert!(policy.delay_for(10, None, &[0xff; 4]) <= 1_000);
+	}
+
+	#[test]
+	fn parse_url_splits_scheme_authority_and_path() {
+		let parts = parse_url("http://example.com/a/b").unwrap();
+		assert_eq!(parts.scheme, "http");
+		assert_eq!(parts.authority, "example.com");
+		assert_eq!(parts.path, "/a/b");
+	}
+
+	#[test]
+	fn parse_url_defaults_path_to_slash_when_absent() {
+		let parts = parse_url("http://example.com").unwrap();
+		assert_eq!(parts.authority, "example.com");
+		assert_eq!(parts.path, "/");
+	}
+
+	#[test]
+	fn parse_url_does_not_fold_query_into_authority() {
+		let parts = parse_url("http://example.com?x=1").unwrap();
+		assert_eq!(parts.authority, "example.com");
+
+		let parts = parse_url("http://example.com#frag").unwrap();
+		assert_eq!(parts.authority, "example.com");
+	}
+
+	#[test]
+	fn same_origin_ignores_path_and_is_case_insensitive() {
+		assert!(same_origin(b"HTTP://Example.com/a", b"http://example.com/b"));
+		assert!(!same_origin(b"http://example.com", b"http://example.org"));
+		assert!(!same_origin(b"http://example.com", b"https://example.com"));
+	}
+
+	#[test]
+	fn resolve_url_handles_absolute_protocol_relative_and_relative_targets() {
+		assert_eq!(
+			resolve_url("http://example.com/a/b", "http://other.com/c").unwrap(),
+			b"http://other.com/c".to_vec(),
+		);
+		assert_eq!(
+			resolve_url("http://example.com/a/b", "//other.com/c").unwrap(),
+			b"http://other.com/c".to_vec(),
+		);
+		assert_eq!(
+			resolve_url("http://example.com/a/b", "/c").unwrap(),
+			b"http://example.com/c".to_vec(),
+		);
+		assert_eq!(
+			resolve_url("http://example.com/a/b", "c").unwrap(),
+			b"http://example.com/a/c".to_vec(),
+		);
+	}
+
+	#[test]
+	fn resolve_url_does_not_mistake_a_query_value_containing_a_scheme_for_an_absolute_url() {
+		assert_eq!(
+			resolve_url("http://example.com/a/b", "/cb?next=http://evil.example").unwrap(),
+			b"http://example.com/cb?next=http://evil.example".to_vec(),
+		);
+	}
+
+	#[test]
+	fn is_redirect_status_matches_3xx_redirect_codes() {
+		for code in &[301, 302, 303, 307, 308] {
+			assert!(is_redirect_status(*code));
+		}
+		assert!(!is_redirect_status(200));
+		assert!(!is_redirect_status(404));
+	}
+
+	#[test]
+	fn method_is_get_or_head_recognises_head_case_insensitively() {
+		assert!(method_is_get_or_head(&Method::Get));
+		assert!(method_is_get_or_head(&Method::Other("head")));
+		assert!(method_is_get_or_head(&Method::Other("HEAD")));
+		assert!(!method_is_get_or_head(&Method::Post));
+	}
+
+	#[test]
+	fn urlencoded_form_percent_encodes_and_joins_with_ampersand() {
+		let mut form = UrlEncodedForm::new();
+		form.append("a b", "1+1=2").append("c", "d");
+		assert_eq!(form.payload, b"a+b=1%2B1%3D2&c=d".to_vec());
+	}
+
+	#[test]
+	fn form_wraps_a_single_part_in_one_boundary_pair() {
+		let mut form = Form::new();
+		form.text("name", "value");
+
+		let boundary = from_utf8(&form.boundary).unwrap().to_owned();
+		let payload = from_utf8(&form.payload).unwrap();
+		let opening = format!("--{}\r\n", boundary);
+		let closing = format!("--{}--\r\n", boundary);
+
+		assert_eq!(payload.matches(&opening).count(), 1);
+		assert!(payload.ends_with(&closing));
+		assert!(payload.contains("Content-Disposition: form-data; name=\"name\"\r\n\r\nvalue\r\n"));
+	}
+
+	#[test]
+	fn form_appends_parts_under_a_shared_terminator() {
+		let mut form = Form::new();
+		form.text("a", "1");
+		form.file("b", Some("b.txt"), Some("text/plain"), b"2");
+
+		let boundary = from_utf8(&form.boundary).unwrap().to_owned();
+		let payload = from_utf8(&form.payload).unwrap();
+		let opening = format!("--{}\r\n", boundary);
+		let closing = format!("--{}--\r\n", boundary);
+
+		assert_eq!(payload.matches(&opening).count(), 2);
+		assert!(payload.ends_with(&closing));
+		assert!(payload.contains("filename=\"b.txt\""));
+		assert!(payload.contains("Content-Type: text/plain\r\n"));
+	}
+
+	#[test]
+	fn form_field_name_cannot_break_out_of_its_quotes() {
+		let mut form = Form::new();
+		form.text("evil\"; filename=\"x", "value");
+
+		let payload = from_utf8(&form.payload).unwrap();
+		assert!(payload.contains("name=\"evil\\\"; filename=\\\"x\""));
+	}
+
+	#[test]
+	fn form_field_name_cannot_inject_a_crlf() {
+		let mut form = Form::new();
+		form.text("a\r\nContent-Disposition: form-data; name=\"b", "value");
+
+		let payload = from_utf8(&form.payload).unwrap();
+		assert!(!payload.contains("\r\nContent-Disposition: form-data; name=\"b"));
+	}
+
+	#[test]
+	fn form_content_type_header_cannot_inject_a_crlf() {
+		let mut form = Form::new();
+		form.file("a", None, Some("text/plain\r\nX-Injected: 1"), b"data");
+
+		let payload = from_utf8(&form.payload).unwrap();
+		assert!(payload.contains("Content-Type: text/plainX-Injected: 1\r\n"));
+		assert!(!payload.contains("\r\nX-Injected"));
+	}
+
 	#[test]
-	fn write_some() {
-		assert_eq!(true, false)
+	fn set_content_type_replaces_rather_than_duplicates() {
+		let mut headers: Vec<(Vec<u8>, Vec<u8>)> = vec![
+			(b"content-type".to_vec(), b"text/plain".to_vec()),
+		];
+		set_content_type(&mut headers, b"application/x-www-form-urlencoded".to_vec());
+
+		assert_eq!(headers.len(), 1);
+		assert_eq!(headers[0].1, b"application/x-www-form-urlencoded".to_vec());
+	}
+
+	#[test]
+	fn base64_encode_pads_to_a_multiple_of_four() {
+		assert_eq!(base64_encode(b""), "");
+		assert_eq!(base64_encode(b"f"), "Zg==");
+		assert_eq!(base64_encode(b"fo"), "Zm8=");
+		assert_eq!(base64_encode(b"foo"), "Zm9v");
+		// The canonical RFC 7617 `Basic` auth example.
+		assert_eq!(base64_encode(b"Aladdin:open sesame"), "QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+	}
+
+	#[test]
+	fn basic_auth_sets_a_base64_authorization_header() {
+		let mut req = Request::<'_, &'static [&'static [u8]]>::new("http://example.com");
+		req.basic_auth("Aladdin", Some("open sesame"));
+
+		assert_eq!(
+			req.headers.last(),
+			Some(&(b"Authorization".to_vec(), b"Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==".to_vec())),
+		);
+	}
+
+	#[test]
+	fn bearer_auth_sets_a_bearer_authorization_header() {
+		let mut req = Request::<'_, &'static [&'static [u8]]>::new("http://example.com");
+		req.bearer_auth("abc123");
+
+		assert_eq!(
+			req.headers.last(),
+			Some(&(b"Authorization".to_vec(), b"Bearer abc123".to_vec())),
+		);
+	}
+
+	fn collect_decoded(decoded: DecodedResponseBody<std::io::Cursor<Vec<u8>>>) -> Vec<u8> {
+		match decoded.collect::<Result<Vec<u8>, Error>>() {
+			Ok(bytes) => bytes,
+			Err(_) => panic!("expected decoding to succeed"),
+		}
+	}
+
+	#[test]
+	fn decoded_response_body_passes_identity_bytes_through_unchanged() {
+		let decoded = DecodedResponseBody::from_reader(std::io::Cursor::new(b"hello".to_vec()), None);
+		assert_eq!(collect_decoded(decoded), b"hello".to_vec());
+	}
+
+	#[test]
+	fn decoded_response_body_inflates_gzip() {
+		use std::io::Write;
+		let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+		encoder.write_all(b"hello gzip").unwrap();
+		let compressed = encoder.finish().unwrap();
+
+		let decoded = DecodedResponseBody::from_reader(std::io::Cursor::new(compressed), Some("gzip"));
+		assert_eq!(collect_decoded(decoded), b"hello gzip".to_vec());
+	}
+
+	#[test]
+	fn decoded_response_body_inflates_deflate() {
+		use std::io::Write;
+		let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+		encoder.write_all(b"hello deflate").unwrap();
+		let compressed = encoder.finish().unwrap();
+
+		let decoded = DecodedResponseBody::from_reader(std::io::Cursor::new(compressed), Some("deflate"));
+		assert_eq!(collect_decoded(decoded), b"hello deflate".to_vec());
+	}
+
+	#[test]
+	fn decoded_response_body_inflates_brotli() {
+		use std::io::Write;
+		let mut compressed = Vec::new();
+		{
+			let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+			encoder.write_all(b"hello brotli").unwrap();
+		}
+
+		let decoded = DecodedResponseBody::from_reader(std::io::Cursor::new(compressed), Some("br"));
+		assert_eq!(collect_decoded(decoded), b"hello brotli".to_vec());
+	}
+
+	#[test]
+	fn decoded_response_body_surfaces_a_truncated_stream_as_a_decode_error_instead_of_panicking() {
+		use std::io::Write;
+		let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+		encoder.write_all(b"hello gzip, now truncated").unwrap();
+		let compressed = encoder.finish().unwrap();
+		let truncated = compressed[..compressed.len() - 4].to_vec();
+
+		let decoded = DecodedResponseBody::from_reader(std::io::Cursor::new(truncated), Some("gzip"));
+		let result = decoded.collect::<Result<Vec<u8>, Error>>();
+		assert!(matches!(result, Err(Error::Decode)));
+	}
+
+	#[test]
+	fn decode_utf8_accepts_valid_utf8_and_rejects_invalid_bytes() {
+		match decode_utf8(b"hello\xE2\x9C\x93".to_vec()) {
+			Ok(text) => assert_eq!(text, "hello\u{2713}"),
+			Err(_) => panic!("expected valid utf8 to decode"),
+		}
+
+		assert!(matches!(decode_utf8(vec![0xff, 0xfe]), Err(Error::Decode)));
+	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn decode_json_parses_valid_json_and_maps_failures_to_decode_errors() {
+		#[derive(serde::Deserialize, PartialEq, Debug)]
+		struct Point {
+			x: i32,
+			y: i32,
+		}
+
+		let point = match decode_json::<Point>(br#"{"x":1,"y":2}"#) {
+			Ok(point) => point,
+			Err(_) => panic!("expected valid json to parse"),
+		};
+		assert_eq!(point, Point { x: 1, y: 2 });
+
+		assert!(matches!(decode_json::<Point>(b"not json"), Err(Error::Decode)));
 	}
 }
\ No newline at end of file